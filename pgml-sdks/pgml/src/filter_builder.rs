@@ -0,0 +1,349 @@
+//! Registered from the crate root via `mod filter_builder;` (not shown in this tree); callers
+//! (e.g. `Collection::vector_search` in `collection.rs`, also not shown) are expected to pass
+//! the filter document to [`FilterBuilder::build`] and splice the resulting clause/params into
+//! their query -- that call site can't be verified from this file alone.
+
+use anyhow::anyhow;
+
+use crate::types::Json;
+
+/// Compiles a MongoDB-style `Json` filter document into a SQL `WHERE`
+/// clause fragment over a JSONB `metadata` column, binding all literal
+/// values as query parameters rather than interpolating them.
+///
+/// Supported operators:
+/// - comparison: `$eq`, `$ne`, `$gt`, `$gte`, `$lt`, `$lte`, `$in`, `$nin`
+/// - logical: `$and`, `$or`, `$not`
+/// - existence: `$exists`
+///
+/// A bare key with no operator (e.g. `{"lang": "en"}`) is sugar for
+/// `{"lang": {"$eq": "en"}}`.
+///
+/// Returns the rendered clause (using `$1`, `$2`, ... placeholders starting
+/// at `starting_param`) and the values to bind to those placeholders, in
+/// order.
+pub struct FilterBuilder {
+    params: Vec<Json>,
+    starting_param: usize,
+}
+
+impl FilterBuilder {
+    pub fn new(starting_param: usize) -> Self {
+        Self {
+            params: Vec::new(),
+            starting_param,
+        }
+    }
+
+    /// Compile `filter` and return the `WHERE`-clause fragment plus the
+    /// ordered bind parameters it references.
+    pub fn build(mut self, filter: &Json) -> anyhow::Result<(String, Vec<Json>)> {
+        let clause = self.build_document(filter)?;
+        Ok((clause, self.params))
+    }
+
+    fn next_placeholder(&mut self, value: Json) -> String {
+        self.params.push(value);
+        format!("${}", self.starting_param + self.params.len() - 1)
+    }
+
+    fn build_document(&mut self, filter: &Json) -> anyhow::Result<String> {
+        let object = filter
+            .0
+            .as_object()
+            .ok_or_else(|| anyhow!("filter document must be a JSON object"))?;
+
+        if object.is_empty() {
+            return Ok("TRUE".to_string());
+        }
+
+        let mut clauses = Vec::with_capacity(object.len());
+        for (key, value) in object.iter() {
+            clauses.push(match key.as_str() {
+                "$and" => self.build_logical("AND", value)?,
+                "$or" => self.build_logical("OR", value)?,
+                "$not" => format!("NOT ({})", self.build_document(&Json(value.clone()))?),
+                _ => self.build_field(key, &Json(value.clone()))?,
+            });
+        }
+
+        Ok(clauses.join(" AND "))
+    }
+
+    fn build_logical(&mut self, join: &str, value: &serde_json::Value) -> anyhow::Result<String> {
+        let array = value
+            .as_array()
+            .ok_or_else(|| anyhow!("$and/$or must be given an array of filter documents"))?;
+        if array.is_empty() {
+            // Vacuous `$and: []` is TRUE, vacuous `$or: []` is FALSE -- `join` on an empty
+            // clause list would otherwise render the invalid SQL `()`.
+            return Ok((if join == "AND" { "TRUE" } else { "FALSE" }).to_string());
+        }
+        let clauses = array
+            .iter()
+            .map(|v| self.build_document(&Json(v.clone())))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        Ok(format!("({})", clauses.join(&format!(" {join} "))))
+    }
+
+    fn build_field(&mut self, key: &str, value: &Json) -> anyhow::Result<String> {
+        // Keys come straight from the caller's Json document, so validate before splicing
+        // them into the path literal -- anything else (e.g. a stray `'`) could break out of
+        // the string and inject arbitrary SQL.
+        if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(anyhow!(
+                "invalid filter key '{key}': only alphanumeric characters and '_' are allowed"
+            ));
+        }
+        let path = format!("metadata #>> '{{{key}}}'");
+
+        // An operator object like `{"$gte": 2020}`. A plain object without
+        // any `$`-prefixed keys is not an operator object -- reject it, as
+        // we have no way to compare a JSONB path to a nested document.
+        if let Some(object) = value.0.as_object() {
+            if object.keys().all(|k| k.starts_with('$')) && !object.is_empty() {
+                let clauses = object
+                    .iter()
+                    .map(|(op, operand)| self.build_operator(&path, op, operand))
+                    .collect::<anyhow::Result<Vec<_>>>()?;
+                return Ok(clauses.join(" AND "));
+            }
+            return Err(anyhow!(
+                "filter value for '{key}' must be a scalar or an operator object"
+            ));
+        }
+
+        // Bare key: sugar for equality.
+        let casted_path = cast_path(&path, &value.0);
+        let placeholder = self.next_placeholder(value.clone());
+        Ok(format!("{casted_path} = {placeholder}"))
+    }
+
+    fn build_operator(
+        &mut self,
+        path: &str,
+        op: &str,
+        operand: &serde_json::Value,
+    ) -> anyhow::Result<String> {
+        // `#>>` always extracts the JSONB path as `text`, so the path needs casting to the
+        // operand's type before comparing -- otherwise e.g. `text >= integer` is rejected by
+        // Postgres at query time.
+        let casted_path = cast_path(path, operand);
+        match op {
+            "$eq" => Ok(format!(
+                "{casted_path} = {}",
+                self.next_placeholder(Json(operand.clone()))
+            )),
+            "$ne" => Ok(format!(
+                "{casted_path} != {}",
+                self.next_placeholder(Json(operand.clone()))
+            )),
+            "$gt" => Ok(format!(
+                "{casted_path} > {}",
+                self.next_placeholder(Json(operand.clone()))
+            )),
+            "$gte" => Ok(format!(
+                "{casted_path} >= {}",
+                self.next_placeholder(Json(operand.clone()))
+            )),
+            "$lt" => Ok(format!(
+                "{casted_path} < {}",
+                self.next_placeholder(Json(operand.clone()))
+            )),
+            "$lte" => Ok(format!(
+                "{casted_path} <= {}",
+                self.next_placeholder(Json(operand.clone()))
+            )),
+            "$in" => self.build_in(path, operand, false),
+            "$nin" => self.build_in(path, operand, true),
+            "$exists" => {
+                let exists = operand
+                    .as_bool()
+                    .ok_or_else(|| anyhow!("$exists must be given a boolean"))?;
+                Ok(if exists {
+                    format!("{path} IS NOT NULL")
+                } else {
+                    format!("{path} IS NULL")
+                })
+            }
+            unknown => Err(anyhow!("unknown filter operator: {unknown}")),
+        }
+    }
+
+    fn build_in(
+        &mut self,
+        path: &str,
+        operand: &serde_json::Value,
+        negate: bool,
+    ) -> anyhow::Result<String> {
+        let array = operand
+            .as_array()
+            .ok_or_else(|| anyhow!("$in/$nin must be given an array"))?;
+        if array.is_empty() {
+            // Nothing is "in" an empty set: `$in: []` is always FALSE, so `$nin: []` (its
+            // negation) is always TRUE. `... IN ()` is invalid SQL, so special-case this
+            // rather than rendering it.
+            return Ok((if negate { "TRUE" } else { "FALSE" }).to_string());
+        }
+        // Cast based on the first element; `$in`/`$nin` arrays are expected to be
+        // homogeneously typed, same as the rest of the operators.
+        let casted_path = cast_path(path, &array[0]);
+        let placeholders = array
+            .iter()
+            .map(|v| self.next_placeholder(Json(v.clone())))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let op = if negate { "NOT IN" } else { "IN" };
+        Ok(format!("{casted_path} {op} ({placeholders})"))
+    }
+}
+
+/// Wraps a `#>>`-extracted (always `text`) path expression in a cast matching `value`'s JSON
+/// type, so the comparison against the bound parameter's native type type-checks.
+fn cast_path(path: &str, value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Number(_) => format!("({path})::numeric"),
+        serde_json::Value::Bool(_) => format!("({path})::boolean"),
+        _ => path.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build(filter: serde_json::Value) -> (String, Vec<Json>) {
+        FilterBuilder::new(1)
+            .build(&Json(filter))
+            .expect("filter should compile")
+    }
+
+    #[test]
+    fn empty_document_is_true() {
+        let (clause, params) = build(serde_json::json!({}));
+        assert_eq!(clause, "TRUE");
+        assert!(params.is_empty());
+    }
+
+    #[test]
+    fn bare_key_is_eq_sugar() {
+        let (clause, params) = build(serde_json::json!({"lang": "en"}));
+        assert_eq!(clause, "metadata #>> '{lang}' = $1");
+        assert_eq!(params, vec![Json(serde_json::json!("en"))]);
+    }
+
+    #[test]
+    fn comparison_operators_cast_numeric() {
+        let (clause, _) = build(serde_json::json!({"year": {"$gte": 2020}}));
+        assert_eq!(clause, "(metadata #>> '{year}')::numeric >= $1");
+    }
+
+    #[test]
+    fn all_comparison_operators() {
+        for (op, sql) in [
+            ("$eq", "="),
+            ("$ne", "!="),
+            ("$gt", ">"),
+            ("$gte", ">="),
+            ("$lt", "<"),
+            ("$lte", "<="),
+        ] {
+            let (clause, _) = build(serde_json::json!({"year": {op: 2020}}));
+            assert_eq!(
+                clause,
+                format!("(metadata #>> '{{year}}')::numeric {sql} $1")
+            );
+        }
+    }
+
+    #[test]
+    fn in_and_nin() {
+        let (clause, params) = build(serde_json::json!({"year": {"$in": [2020, 2021]}}));
+        assert_eq!(clause, "(metadata #>> '{year}')::numeric IN ($1, $2)");
+        assert_eq!(params.len(), 2);
+
+        let (clause, _) = build(serde_json::json!({"year": {"$nin": [2020]}}));
+        assert_eq!(clause, "(metadata #>> '{year}')::numeric NOT IN ($1)");
+    }
+
+    #[test]
+    fn empty_in_is_false_empty_nin_is_true() {
+        let (clause, params) = build(serde_json::json!({"year": {"$in": []}}));
+        assert_eq!(clause, "FALSE");
+        assert!(params.is_empty());
+
+        let (clause, params) = build(serde_json::json!({"year": {"$nin": []}}));
+        assert_eq!(clause, "TRUE");
+        assert!(params.is_empty());
+    }
+
+    #[test]
+    fn exists() {
+        let (clause, params) = build(serde_json::json!({"year": {"$exists": true}}));
+        assert_eq!(clause, "metadata #>> '{year}' IS NOT NULL");
+        assert!(params.is_empty());
+
+        let (clause, _) = build(serde_json::json!({"year": {"$exists": false}}));
+        assert_eq!(clause, "metadata #>> '{year}' IS NULL");
+    }
+
+    #[test]
+    fn and_or_not_and_nesting() {
+        let (clause, params) = build(serde_json::json!({
+            "$and": [{"lang": "en"}, {"year": {"$gte": 2020}}],
+        }));
+        assert_eq!(
+            clause,
+            "(metadata #>> '{lang}' = $1 AND (metadata #>> '{year}')::numeric >= $2)"
+        );
+        assert_eq!(params.len(), 2);
+
+        let (clause, _) = build(serde_json::json!({
+            "$or": [{"lang": "en"}, {"lang": "fr"}],
+        }));
+        assert_eq!(
+            clause,
+            "(metadata #>> '{lang}' = $1 OR metadata #>> '{lang}' = $2)"
+        );
+
+        let (clause, _) = build(serde_json::json!({"$not": {"lang": "en"}}));
+        assert_eq!(clause, "NOT (metadata #>> '{lang}' = $1)");
+    }
+
+    #[test]
+    fn empty_and_is_true_empty_or_is_false() {
+        let (clause, params) = build(serde_json::json!({"$and": []}));
+        assert_eq!(clause, "TRUE");
+        assert!(params.is_empty());
+
+        let (clause, params) = build(serde_json::json!({"$or": []}));
+        assert_eq!(clause, "FALSE");
+        assert!(params.is_empty());
+    }
+
+    #[test]
+    fn implicit_and_across_top_level_keys() {
+        let (clause, params) = build(serde_json::json!({"lang": "en", "year": {"$gte": 2020}}));
+        assert_eq!(
+            clause,
+            "metadata #>> '{lang}' = $1 AND (metadata #>> '{year}')::numeric >= $2"
+        );
+        assert_eq!(params.len(), 2);
+    }
+
+    #[test]
+    fn rejects_invalid_filter_keys() {
+        let err = FilterBuilder::new(1)
+            .build(&Json(serde_json::json!({"bad'key": "en"})))
+            .unwrap_err();
+        assert!(err.to_string().contains("invalid filter key"));
+    }
+
+    #[test]
+    fn rejects_unknown_operator() {
+        let err = FilterBuilder::new(1)
+            .build(&Json(serde_json::json!({"year": {"$bogus": 1}})))
+            .unwrap_err();
+        assert!(err.to_string().contains("unknown filter operator"));
+    }
+}
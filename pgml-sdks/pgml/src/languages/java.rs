@@ -0,0 +1,214 @@
+use futures::StreamExt;
+use jni::objects::{JObject, JValue};
+use jni::JNIEnv;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+use crate::{pipeline::PipelineSyncData, rag::RAGStream, transformer_pipeline::TransformerStream, types::Json};
+
+////////////////////////////////////////////////////////////////////////////////
+// Rust to Java ////////////////////////////////////////////////////////////////
+////////////////////////////////////////////////////////////////////////////////
+
+/// JNI equivalent of `IntoPy<PyObject> for Json`.
+pub fn json_to_jobject<'local>(
+    env: &mut JNIEnv<'local>,
+    value: &Json,
+) -> jni::errors::Result<JObject<'local>> {
+    match &value.0 {
+        serde_json::Value::Null => Ok(JObject::null()),
+        serde_json::Value::Bool(x) => env.new_object(
+            "java/lang/Boolean",
+            "(Z)V",
+            &[JValue::Bool(*x as u8).as_jni()],
+        ),
+        serde_json::Value::Number(x) => {
+            let d = x.as_f64().expect("Error converting number to f64 for JNI");
+            env.new_object("java/lang/Double", "(D)V", &[JValue::Double(d).as_jni()])
+        }
+        serde_json::Value::String(x) => Ok(env.new_string(x)?.into()),
+        serde_json::Value::Array(x) => {
+            let list = env.new_object("java/util/ArrayList", "()V", &[])?;
+            for v in x {
+                let element = json_to_jobject(env, &Json(v.clone()))?;
+                env.call_method(
+                    &list,
+                    "add",
+                    "(Ljava/lang/Object;)Z",
+                    &[JValue::Object(&element).as_jni()],
+                )?;
+            }
+            Ok(list)
+        }
+        serde_json::Value::Object(x) => {
+            let map = env.new_object("java/util/HashMap", "()V", &[])?;
+            for (k, v) in x {
+                let key = env.new_string(k)?;
+                let value = json_to_jobject(env, &Json(v.clone()))?;
+                env.call_method(
+                    &map,
+                    "put",
+                    "(Ljava/lang/Object;Ljava/lang/Object;)Ljava/lang/Object;",
+                    &[JValue::Object(&key).as_jni(), JValue::Object(&value).as_jni()],
+                )?;
+            }
+            Ok(map)
+        }
+    }
+}
+
+pub fn pipeline_sync_data_to_jobject<'local>(
+    env: &mut JNIEnv<'local>,
+    value: PipelineSyncData,
+) -> jni::errors::Result<JObject<'local>> {
+    json_to_jobject(env, &Json::from(value))
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Java to Rust ////////////////////////////////////////////////////////////////
+////////////////////////////////////////////////////////////////////////////////
+
+/// JNI equivalent of `FromPyObject<'_> for Json`.
+pub fn jobject_to_json<'local>(
+    env: &mut JNIEnv<'local>,
+    object: &JObject<'local>,
+) -> jni::errors::Result<Json> {
+    if object.is_null() {
+        return Ok(Json(serde_json::Value::Null));
+    }
+    if env.is_instance_of(object, "java/lang/Boolean")? {
+        let value = env.call_method(object, "booleanValue", "()Z", &[])?.z()?;
+        return Ok(Json(serde_json::Value::Bool(value)));
+    }
+    if env.is_instance_of(object, "java/lang/Number")? {
+        let value = env.call_method(object, "doubleValue", "()D", &[])?.d()?;
+        let value = serde_json::Number::from_f64(value)
+            .expect("Could not convert f64 to serde_json::Number");
+        return Ok(Json(serde_json::Value::Number(value)));
+    }
+    if env.is_instance_of(object, "java/lang/String")? {
+        let value: String = env.get_string((&(*object)).into())?.into();
+        return Ok(Json(serde_json::Value::String(value)));
+    }
+    if env.is_instance_of(object, "java/util/List")? {
+        let size = env.call_method(object, "size", "()I", &[])?.i()?;
+        let mut values = Vec::with_capacity(size as usize);
+        for i in 0..size {
+            let element = env
+                .call_method(object, "get", "(I)Ljava/lang/Object;", &[JValue::Int(i).as_jni()])?
+                .l()?;
+            values.push(jobject_to_json(env, &element)?.0);
+        }
+        return Ok(Json(serde_json::Value::Array(values)));
+    }
+    if env.is_instance_of(object, "java/util/Map")? {
+        let entry_set = env
+            .call_method(object, "entrySet", "()Ljava/util/Set;", &[])?
+            .l()?;
+        let iterator = env
+            .call_method(&entry_set, "iterator", "()Ljava/util/Iterator;", &[])?
+            .l()?;
+        let mut map = serde_json::Map::new();
+        while env.call_method(&iterator, "hasNext", "()Z", &[])?.z()? {
+            let entry = env
+                .call_method(&iterator, "next", "()Ljava/lang/Object;", &[])?
+                .l()?;
+            let key = env
+                .call_method(&entry, "getKey", "()Ljava/lang/Object;", &[])?
+                .l()?;
+            let key: String = env.get_string((&key).into())?.into();
+            let value = env
+                .call_method(&entry, "getValue", "()Ljava/lang/Object;", &[])?
+                .l()?;
+            map.insert(key, jobject_to_json(env, &value)?.0);
+        }
+        return Ok(Json(serde_json::Value::Object(map)));
+    }
+    Err(jni::errors::Error::WrongJValueType(
+        "recognized JSON type",
+        "unsupported Java object",
+    ))
+}
+
+pub fn jobject_to_pipeline_sync_data<'local>(
+    env: &mut JNIEnv<'local>,
+    object: &JObject<'local>,
+) -> jni::errors::Result<PipelineSyncData> {
+    Ok(jobject_to_json(env, object)?.into())
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Streams /////////////////////////////////////////////////////////////////////
+////////////////////////////////////////////////////////////////////////////////
+
+/// Java counterpart to `TransformerStreamPython`; JNI has no async equivalent, so a thin Java
+/// iterator wrapper blocks the calling thread on `next_blocking` instead.
+pub struct TransformerStreamJava {
+    wrapped: Arc<Mutex<TransformerStream>>,
+    runtime: Arc<tokio::runtime::Handle>,
+}
+
+impl TransformerStreamJava {
+    pub fn new(stream: TransformerStream, runtime: Arc<tokio::runtime::Handle>) -> Self {
+        Self {
+            wrapped: Arc::new(Mutex::new(stream)),
+            runtime,
+        }
+    }
+
+    pub fn next_blocking(&self, env: &mut JNIEnv<'_>) -> jni::errors::Result<Option<String>> {
+        let wrapped = self.wrapped.clone();
+        let result = self.runtime.block_on(async move {
+            let mut stream = wrapped.lock().await;
+            stream.next().await
+        });
+        match result {
+            Some(Ok(token)) => Ok(Some(token)),
+            Some(Err(e)) => {
+                // `MethodNotFound` means "JNI couldn't resolve a method" -- it's the wrong
+                // shape for a real stream error, which would otherwise surface to Java callers
+                // as a bogus `NoSuchMethodError`. Throw a real exception instead.
+                env.throw_new(
+                    "java/lang/RuntimeException",
+                    format!("error calling next on TransformerStream: {e}"),
+                )?;
+                Err(jni::errors::Error::JavaException)
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+/// Java counterpart to `RAGStreamPython`, likewise blocking.
+pub struct RAGStreamJava {
+    wrapped: Arc<Mutex<RAGStream>>,
+    runtime: Arc<tokio::runtime::Handle>,
+}
+
+impl RAGStreamJava {
+    pub fn new(stream: RAGStream, runtime: Arc<tokio::runtime::Handle>) -> Self {
+        Self {
+            wrapped: Arc::new(Mutex::new(stream)),
+            runtime,
+        }
+    }
+
+    pub fn next_blocking(&self, env: &mut JNIEnv<'_>) -> jni::errors::Result<Option<String>> {
+        let wrapped = self.wrapped.clone();
+        let result = self.runtime.block_on(async move {
+            let mut stream = wrapped.lock().await;
+            stream.next().await
+        });
+        match result {
+            Some(Ok(token)) => Ok(Some(token)),
+            Some(Err(e)) => {
+                env.throw_new(
+                    "java/lang/RuntimeException",
+                    format!("error generating RAG token: {e}"),
+                )?;
+                Err(jni::errors::Error::JavaException)
+            }
+            None => Ok(None),
+        }
+    }
+}
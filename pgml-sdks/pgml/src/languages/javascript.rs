@@ -0,0 +1,169 @@
+use futures::StreamExt;
+use napi::bindgen_prelude::*;
+use napi::{JsNumber, JsObject, JsString, JsUnknown, ValueType};
+use napi_derive::napi;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+use crate::{pipeline::PipelineSyncData, rag::RAGStream, transformer_pipeline::TransformerStream, types::Json};
+
+////////////////////////////////////////////////////////////////////////////////
+// Rust to JS //////////////////////////////////////////////////////////////////
+////////////////////////////////////////////////////////////////////////////////
+
+impl ToNapiValue for Json {
+    unsafe fn to_napi_value(env: napi::sys::napi_env, val: Self) -> Result<napi::sys::napi_value> {
+        let env = Env::from_raw(env);
+        match val.0 {
+            serde_json::Value::Bool(x) => ToNapiValue::to_napi_value(env.raw(), x),
+            serde_json::Value::Number(x) => {
+                if x.is_f64() {
+                    ToNapiValue::to_napi_value(env.raw(), x.as_f64().unwrap())
+                } else {
+                    ToNapiValue::to_napi_value(env.raw(), x.as_i64().unwrap())
+                }
+            }
+            serde_json::Value::String(x) => ToNapiValue::to_napi_value(env.raw(), x),
+            serde_json::Value::Array(x) => {
+                let mut array = env.create_array_with_length(x.len())?;
+                for (i, v) in x.into_iter().enumerate() {
+                    array.set_element(i as u32, env.to_js_value(&Json(v))?)?;
+                }
+                ToNapiValue::to_napi_value(env.raw(), array)
+            }
+            serde_json::Value::Object(x) => {
+                let mut object = env.create_object()?;
+                for (k, v) in x.into_iter() {
+                    object.set_named_property(&k, env.to_js_value(&Json(v))?)?;
+                }
+                ToNapiValue::to_napi_value(env.raw(), object)
+            }
+            serde_json::Value::Null => ToNapiValue::to_napi_value(env.raw(), env.get_null()?),
+        }
+    }
+}
+
+impl ToNapiValue for PipelineSyncData {
+    unsafe fn to_napi_value(env: napi::sys::napi_env, val: Self) -> Result<napi::sys::napi_value> {
+        ToNapiValue::to_napi_value(env, Json::from(val))
+    }
+}
+
+/// JS counterpart to `TransformerStreamPython`, exposed as an async `.next()` method.
+#[napi]
+pub struct TransformerStreamJavascript {
+    wrapped: Arc<Mutex<TransformerStream>>,
+}
+
+#[napi]
+impl TransformerStreamJavascript {
+    #[napi]
+    pub async fn next(&self) -> Result<Option<String>> {
+        let mut ts = self.wrapped.lock().await;
+        match ts.next().await {
+            Some(Ok(token)) => Ok(Some(token)),
+            Some(Err(e)) => Err(Error::from_reason(format!(
+                "error calling next on TransformerStream: {e}"
+            ))),
+            None => Ok(None),
+        }
+    }
+}
+
+impl From<TransformerStream> for TransformerStreamJavascript {
+    fn from(stream: TransformerStream) -> Self {
+        Self {
+            wrapped: Arc::new(Mutex::new(stream)),
+        }
+    }
+}
+
+/// JS-side counterpart to `RAGStreamPython`.
+#[napi]
+pub struct RAGStreamJavascript {
+    wrapped: Arc<Mutex<RAGStream>>,
+}
+
+#[napi]
+impl RAGStreamJavascript {
+    #[napi]
+    pub async fn next(&self) -> Result<Option<String>> {
+        let mut rs = self.wrapped.lock().await;
+        match rs.next().await {
+            Some(Ok(token)) => Ok(Some(token)),
+            Some(Err(e)) => Err(Error::from_reason(format!(
+                "error generating RAG token: {e}"
+            ))),
+            None => Ok(None),
+        }
+    }
+}
+
+impl From<RAGStream> for RAGStreamJavascript {
+    fn from(stream: RAGStream) -> Self {
+        Self {
+            wrapped: Arc::new(Mutex::new(stream)),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// JS to Rust //////////////////////////////////////////////////////////////////
+////////////////////////////////////////////////////////////////////////////////
+
+impl FromNapiValue for Json {
+    unsafe fn from_napi_value(env: napi::sys::napi_env, napi_val: napi::sys::napi_value) -> Result<Self> {
+        let value = JsUnknown::from_raw(env, napi_val)?;
+        match value.get_type()? {
+            ValueType::Boolean => Ok(Json(serde_json::Value::Bool(
+                value.coerce_to_bool()?.get_value()?,
+            ))),
+            ValueType::Number => {
+                let n: JsNumber = value.coerce_to_number()?;
+                let n = n.get_double()?;
+                Ok(Json(serde_json::Value::Number(
+                    serde_json::Number::from_f64(n)
+                        .expect("Could not convert f64 to serde_json::Number"),
+                )))
+            }
+            ValueType::String => Ok(Json(serde_json::Value::String(
+                value.coerce_to_string()?.into_utf8()?.as_str()?.to_owned(),
+            ))),
+            ValueType::Object => {
+                let object: JsObject = value.try_into()?;
+                if object.is_array()? {
+                    let length = object.get_array_length()?;
+                    let mut values = Vec::with_capacity(length as usize);
+                    for i in 0..length {
+                        let element: JsUnknown = object.get_element(i)?;
+                        values.push(Json::from_napi_value(env, element.raw())?.0);
+                    }
+                    Ok(Json(serde_json::Value::Array(values)))
+                } else {
+                    // `get_property_names` returns a JS array of `JsString`s, not Rust
+                    // `String`s -- walk it the same way the array branch above walks elements.
+                    let keys = object.get_property_names()?;
+                    let length = keys.get_array_length()?;
+                    let mut map = serde_json::Map::new();
+                    for i in 0..length {
+                        let key: JsString = keys.get_element(i)?;
+                        let key = key.into_utf8()?.as_str()?.to_owned();
+                        let value: JsUnknown = object.get_named_property(&key)?;
+                        map.insert(key, Json::from_napi_value(env, value.raw())?.0);
+                    }
+                    Ok(Json(serde_json::Value::Object(map)))
+                }
+            }
+            ValueType::Null | ValueType::Undefined => Ok(Json(serde_json::Value::Null)),
+            other => Err(Error::from_reason(format!(
+                "Unsupported JS type for JSON conversion: {other:?}"
+            ))),
+        }
+    }
+}
+
+impl FromNapiValue for PipelineSyncData {
+    unsafe fn from_napi_value(env: napi::sys::napi_env, napi_val: napi::sys::napi_value) -> Result<Self> {
+        Ok(Json::from_napi_value(env, napi_val)?.into())
+    }
+}
@@ -0,0 +1,10 @@
+//! Per-language bridge implementations, selected by cargo feature.
+
+#[cfg(feature = "python")]
+pub mod python;
+
+#[cfg(feature = "javascript")]
+pub mod javascript;
+
+#[cfg(feature = "java")]
+pub mod java;
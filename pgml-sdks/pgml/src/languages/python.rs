@@ -1,4 +1,6 @@
 use futures::StreamExt;
+use numpy::PyArray1;
+use pyo3::buffer::PyBuffer;
 use pyo3::conversion::IntoPy;
 use pyo3::types::{PyDict, PyFloat, PyInt, PyList, PyString};
 use pyo3::{prelude::*, types::PyBool};
@@ -6,7 +8,10 @@ use std::sync::Arc;
 
 use rust_bridge::python::CustomInto;
 
-use crate::{pipeline::PipelineSyncData, transformer_pipeline::TransformerStream, types::Json};
+use crate::{
+    pipeline::PipelineSyncData, rag::RAGStream, transformer_pipeline::TransformerStream,
+    types::Json,
+};
 
 ////////////////////////////////////////////////////////////////////////////////
 // Rust to PY //////////////////////////////////////////////////////////////////
@@ -97,6 +102,52 @@ impl IntoPy<PyObject> for TransformerStream {
     }
 }
 
+#[pyclass]
+#[derive(Clone)]
+struct RAGStreamPython {
+    wrapped: Arc<tokio::sync::Mutex<RAGStream>>,
+}
+
+#[pymethods]
+impl RAGStreamPython {
+    fn __aiter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __anext__<'p>(slf: PyRefMut<'_, Self>, py: Python<'p>) -> PyResult<Option<PyObject>> {
+        let rs = slf.wrapped.clone();
+        let fut = pyo3_asyncio::tokio::future_into_py(py, async move {
+            let mut rs = rs.lock().await;
+            if let Some(o) = rs.next().await {
+                let token = o.map_err(|e| {
+                    pyo3::exceptions::PyRuntimeError::new_err(format!(
+                        "error generating RAG token: {e}"
+                    ))
+                })?;
+                Ok(Some(Python::with_gil(|py| token.to_object(py))))
+            } else {
+                Err(pyo3::exceptions::PyStopAsyncIteration::new_err(
+                    "stream exhausted",
+                ))
+            }
+        })?;
+        Ok(Some(fut.into()))
+    }
+}
+
+impl IntoPy<PyObject> for RAGStream {
+    fn into_py(self, py: Python) -> PyObject {
+        let f: Py<RAGStreamPython> = Py::new(
+            py,
+            RAGStreamPython {
+                wrapped: Arc::new(tokio::sync::Mutex::new(self)),
+            },
+        )
+        .expect("Error converting RAGStream to RAGStreamPython");
+        f.to_object(py)
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // PY to Rust //////////////////////////////////////////////////////////////////
 ////////////////////////////////////////////////////////////////////////////////
@@ -133,6 +184,40 @@ impl FromPyObject<'_> for Json {
                 json_values.push(v.0);
             }
             Ok(Self(serde_json::Value::Array(json_values)))
+        } else if let Some(values) = extract_integer_buffer(ob) {
+            let json_values = values.into_iter().map(|v| v.into()).collect();
+            Ok(Self(serde_json::Value::Array(json_values)))
+        } else if let Some(values) = extract_float_buffer(ob) {
+            let json_values = values
+                .into_iter()
+                .map(|v| {
+                    serde_json::value::Number::from_f64(v)
+                        .map(serde_json::Value::Number)
+                        .unwrap_or(serde_json::Value::Null)
+                })
+                .collect();
+            Ok(Self(serde_json::Value::Array(json_values)))
+        } else if let Ok(iter) = ob.iter() {
+            // Slow, per-element fallback for sequence-like objects (e.g.
+            // non-contiguous or mixed-dtype NumPy arrays) that weren't
+            // handled by the contiguous buffer-protocol fast paths above.
+            let mut json_values = Vec::new();
+            for v in iter {
+                json_values.push(Json::extract(v?)?.0);
+            }
+            Ok(Self(serde_json::Value::Array(json_values)))
+        } else if let Ok(value) = i64::extract(ob) {
+            // NumPy integer scalars (e.g. `np.int64`) don't subclass `int`, so they fall
+            // through the `PyInt` check above; coerce them here rather than panicking.
+            Ok(Self(serde_json::Value::Number(value.into())))
+        } else if let Ok(value) = f64::extract(ob) {
+            // Likewise for NumPy float scalars that aren't caught by `PyFloat`. NaN/Infinity
+            // have no JSON representation, so map them to `Null` rather than panicking, same
+            // as the float-buffer array path above.
+            let value = serde_json::value::Number::from_f64(value)
+                .map(serde_json::Value::Number)
+                .unwrap_or(serde_json::Value::Null);
+            Ok(Self(value))
         } else {
             if ob.is_none() {
                 return Ok(Self(serde_json::Value::Null));
@@ -142,6 +227,63 @@ impl FromPyObject<'_> for Json {
     }
 }
 
+/// Bulk-reads a contiguous, one-dimensional `i32`/`i64` buffer into a single `Vec<i64>`,
+/// preserving exact integer values. Returns `None` for non-integer, non-contiguous, or
+/// multi-dimensional buffers so the caller can fall back to the per-element path instead
+/// of silently flattening e.g. a 2D `numpy.ndarray`.
+fn extract_integer_buffer(ob: &PyAny) -> Option<Vec<i64>> {
+    if let Ok(buf) = PyBuffer::<i64>::get(ob) {
+        if buf.is_c_contiguous() && buf.dimensions() == 1 {
+            if let Ok(values) = buf.to_vec(ob.py()) {
+                return Some(values);
+            }
+        }
+    }
+    if let Ok(buf) = PyBuffer::<i32>::get(ob) {
+        if buf.is_c_contiguous() && buf.dimensions() == 1 {
+            if let Ok(values) = buf.to_vec(ob.py()) {
+                return Some(values.into_iter().map(i64::from).collect());
+            }
+        }
+    }
+    None
+}
+
+/// Bulk-reads a contiguous, one-dimensional `f32`/`f64` buffer (e.g. a `numpy.ndarray`) into
+/// a single `Vec<f64>`, avoiding the per-element `PyList` round trip. Returns `None` for
+/// non-float, non-contiguous, or multi-dimensional buffers -- a 2D+ array must go through the
+/// `ob.iter()` fallback instead of being silently flattened.
+fn extract_float_buffer(ob: &PyAny) -> Option<Vec<f64>> {
+    if let Ok(buf) = PyBuffer::<f64>::get(ob) {
+        if buf.is_c_contiguous() && buf.dimensions() == 1 {
+            if let Ok(values) = buf.to_vec(ob.py()) {
+                return Some(values);
+            }
+        }
+    }
+    if let Ok(buf) = PyBuffer::<f32>::get(ob) {
+        if buf.is_c_contiguous() && buf.dimensions() == 1 {
+            if let Ok(values) = buf.to_vec(ob.py()) {
+                return Some(values.into_iter().map(|v| v as f64).collect());
+            }
+        }
+    }
+    None
+}
+
+impl Json {
+    /// Like `into_py`, but materializes a numeric array as a NumPy `ndarray` instead of a
+    /// `PyList`. Falls back to `into_py` for anything else.
+    pub fn into_py_numpy(self, py: Python) -> PyObject {
+        if let serde_json::Value::Array(values) = &self.0 {
+            if let Some(floats) = values.iter().map(|v| v.as_f64()).collect::<Option<Vec<_>>>() {
+                return PyArray1::from_vec(py, floats).to_object(py);
+            }
+        }
+        self.into_py(py)
+    }
+}
+
 impl FromPyObject<'_> for PipelineSyncData {
     fn extract(ob: &PyAny) -> PyResult<Self> {
         let json = Json::extract(ob)?;
@@ -0,0 +1,150 @@
+//! Registered from the crate root via `mod rag;` (not shown in this tree).
+
+use std::sync::Arc;
+
+use anyhow::Context;
+use futures::Stream;
+use tokio::sync::Mutex;
+
+use crate::{
+    collection::Collection,
+    transformer_pipeline::{TransformerPipeline, TransformerStream},
+    types::Json,
+};
+
+/// Default template: `{context}` and `{question}` are substituted in.
+pub const DEFAULT_RAG_PROMPT_TEMPLATE: &str = "Answer the question using only the context below.\n\nContext:\n{context}\n\nQuestion: {question}\nAnswer:";
+
+#[derive(Debug, Clone)]
+pub struct RAGSourceChunk {
+    pub chunk: Json,
+    pub score: f64,
+}
+
+/// Result of [`Collection::build_rag_query`]: a rendered prompt plus the
+/// chunks retrieved to build it.
+pub struct RAGQuery {
+    pub prompt: String,
+    pub source_chunks: Vec<RAGSourceChunk>,
+}
+
+/// A streaming RAG response: the generation [`TransformerStream`] plus the
+/// source chunks it was generated from.
+pub struct RAGStream {
+    wrapped: TransformerStream,
+    pub source_chunks: Vec<RAGSourceChunk>,
+}
+
+impl RAGStream {
+    fn new(wrapped: TransformerStream, source_chunks: Vec<RAGSourceChunk>) -> Self {
+        Self {
+            wrapped,
+            source_chunks,
+        }
+    }
+}
+
+impl Stream for RAGStream {
+    type Item = anyhow::Result<String>;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        std::pin::Pin::new(&mut self.wrapped).poll_next(cx)
+    }
+}
+
+impl Collection {
+    /// Runs vector search for `query` and renders the results into
+    /// `template` (or [`DEFAULT_RAG_PROMPT_TEMPLATE`] if `None`).
+    pub async fn build_rag_query(
+        &self,
+        query: &str,
+        template: Option<&str>,
+        top_k: Option<i64>,
+        vector_search_filter: Option<Json>,
+    ) -> anyhow::Result<RAGQuery> {
+        let results = self
+            .vector_search(query, None, top_k, vector_search_filter, None)
+            .await
+            .context("error running vector search for RAG query")?;
+
+        let source_chunks: Vec<RAGSourceChunk> = results
+            .into_iter()
+            .map(|r| RAGSourceChunk {
+                score: r
+                    .0
+                    .get("score")
+                    .and_then(|s| s.as_f64())
+                    .unwrap_or_default(),
+                chunk: r,
+            })
+            .collect();
+
+        let context = source_chunks
+            .iter()
+            .map(|c| c.chunk.0.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let prompt = template
+            .unwrap_or(DEFAULT_RAG_PROMPT_TEMPLATE)
+            .replace("{context}", &context)
+            .replace("{question}", query);
+
+        Ok(RAGQuery {
+            prompt,
+            source_chunks,
+        })
+    }
+
+    /// Like [`Collection::rag`], but streams the answer token by token.
+    pub async fn rag_stream(
+        &self,
+        query: &str,
+        model: &TransformerPipeline,
+        template: Option<&str>,
+        top_k: Option<i64>,
+        vector_search_filter: Option<Json>,
+    ) -> anyhow::Result<RAGStream> {
+        let rag_query = self
+            .build_rag_query(query, template, top_k, vector_search_filter)
+            .await?;
+        let stream = model
+            .stream(&rag_query.prompt)
+            .await
+            .context("error starting generation stream for RAG query")?;
+        Ok(RAGStream::new(stream, rag_query.source_chunks))
+    }
+
+    /// Retrieves context, generates an answer, and returns it with its
+    /// source chunks as [`Json`].
+    pub async fn rag(
+        &self,
+        query: &str,
+        model: &TransformerPipeline,
+        template: Option<&str>,
+        top_k: Option<i64>,
+        vector_search_filter: Option<Json>,
+    ) -> anyhow::Result<Json> {
+        let rag_query = self
+            .build_rag_query(query, template, top_k, vector_search_filter)
+            .await?;
+        let answer = model
+            .generate(&rag_query.prompt)
+            .await
+            .context("error generating RAG answer")?;
+
+        Ok(Json(serde_json::json!({
+            "answer": answer,
+            "sources": rag_query
+                .source_chunks
+                .into_iter()
+                .map(|c| c.chunk.0)
+                .collect::<Vec<_>>(),
+        })))
+    }
+}
+
+pub type SharedRAGStream = Arc<Mutex<RAGStream>>;
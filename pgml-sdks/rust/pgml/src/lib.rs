@@ -4,9 +4,11 @@
 //!
 //! With this SDK, you can seamlessly manage various database tables related to documents, text chunks, text splitters, LLM (Language Model) models, and embeddings. By leveraging the SDK's capabilities, you can efficiently index LLM embeddings using PgVector for fast and accurate queries.
 
-use log::{Level, LevelFilter, Metadata, Record, SetLoggerError};
 use pyo3::prelude::*;
+use sqlx::postgres::{PgPool, PgPoolOptions};
+use std::sync::OnceLock;
 use tokio::runtime::{Builder, Runtime};
+use tracing_subscriber::EnvFilter;
 
 mod collection;
 mod database;
@@ -24,54 +26,97 @@ use database::DatabasePython;
 // logger, but because we are used by programs in Python and other languages that do
 // not have the ability to do that, we init it for those languages, but leave it uninitialized when
 // used natively with rust
-struct SimpleLogger;
-
-static LOGGER: SimpleLogger = SimpleLogger;
-
-impl log::Log for SimpleLogger {
-    fn enabled(&self, metadata: &Metadata) -> bool {
-        metadata.level() <= Level::Info
-    }
-
-    fn log(&self, record: &Record) {
-        if self.enabled(record.metadata()) {
-            println!("{} - {}", record.level(), record.args());
+static TRACING_INIT: std::sync::Once = std::sync::Once::new();
+
+/// Installs the global `tracing` subscriber at most once per process. `level` is a standard
+/// `tracing` filter directive (e.g. `"info"`, `"pgml=debug"`); `json` selects JSON output.
+fn init_logger(level: &str, json: bool) {
+    TRACING_INIT.call_once(|| {
+        let filter = EnvFilter::try_new(level).unwrap_or_else(|_| EnvFilter::new("info"));
+        let subscriber = tracing_subscriber::fmt().with_env_filter(filter);
+        if json {
+            subscriber.json().init();
+        } else {
+            subscriber.init();
         }
-    }
-
-    fn flush(&self) {}
+    });
 }
 
-fn init_logger(level: LevelFilter) -> Result<(), SetLoggerError> {
-    log::set_logger(&LOGGER).map(|()| log::set_max_level(level))
+// Normally the global async runtime is handled by tokio but because we are a library being called
+// by javascript and other langauges, we occasionally need to handle it ourselves. A `OnceLock`
+// gives us a runtime that's built exactly once and shared across every `Database`/`Collection`
+// without any `unsafe`, unlike the single-worker `static mut` this replaced.
+static RUNTIME: OnceLock<Runtime> = OnceLock::new();
+
+/// One worker per available core, used when the caller doesn't specify a count.
+fn default_worker_threads() -> usize {
+    std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
 }
 
-// Normally the global async runtime is handled by tokio but because we are a library being called
-// by javascript and other langauges, we occasionally need to handle it ourselves
-static mut RUNTIME: Option<Runtime> = None;
+/// Returns the shared multi-threaded runtime, building it on first call. Later calls ignore
+/// `worker_threads` and return the already-built runtime.
+fn get_or_set_runtime<'a>(worker_threads: Option<usize>) -> &'a Runtime {
+    RUNTIME.get_or_init(|| {
+        Builder::new_multi_thread()
+            .worker_threads(worker_threads.unwrap_or_else(default_worker_threads))
+            .enable_all()
+            .build()
+            .expect("Error building the shared tokio runtime")
+    })
+}
 
-fn get_or_set_runtime<'a>() -> &'a Runtime {
-    unsafe {
-        if let Some(r) = &RUNTIME {
-            r
-        } else {
-            let runtime = Builder::new_current_thread()
-                .worker_threads(1)
-                .enable_all()
-                .build()
-                .unwrap();
-            RUNTIME = Some(runtime);
-            get_or_set_runtime()
+// A shared, bounded connection pool so concurrent `vector_search`/`generate_embeddings` calls
+// reuse connections instead of each opening a fresh one. Built once, like the runtime above.
+static POOL: OnceLock<PgPool> = OnceLock::new();
+static POOL_CONNECTION_STRING: OnceLock<String> = OnceLock::new();
+
+/// Returns the shared connection pool, creating it on first call. Later calls ignore both
+/// arguments and return the already-built pool; a mismatched `connection_string` just warns.
+///
+/// Not yet called from `Database::new`/`Collection` query paths -- that wiring belongs in
+/// `database.rs`/`collection.rs`, neither of which is part of this tree, so those types still
+/// open their own connections rather than drawing from this pool. Exercised here only by the
+/// `shared_pool_is_reused_across_calls` test below until that wiring lands.
+async fn get_or_set_pool(connection_string: &str, max_connections: Option<u32>) -> &'static PgPool {
+    if let Some(pool) = POOL.get() {
+        if POOL_CONNECTION_STRING.get().map(String::as_str) != Some(connection_string) {
+            tracing::warn!(
+                "get_or_set_pool called with a different connection string than the shared \
+                 pool was built with; reusing the existing pool regardless"
+            );
         }
+        return pool;
     }
+    let pool = PgPoolOptions::new()
+        .max_connections(max_connections.unwrap_or(10))
+        .connect(connection_string)
+        .await
+        .expect("Error building the shared Postgres connection pool");
+    let _ = POOL_CONNECTION_STRING.set(connection_string.to_string());
+    // Another thread may have raced us to build the pool; if so, keep theirs and drop ours.
+    let _ = POOL.set(pool);
+    POOL.get().expect("Pool was just set above")
 }
 
 // This is the only piece for the python library still done by hand and not in the proc macros
 #[pymodule]
 fn pgml(_py: Python, m: &PyModule) -> PyResult<()> {
-    // We may want to move this into the new function in the DatabasePython struct and give the
-    // user the oppertunity to pass in the log level filter
-    init_logger(LevelFilter::Error).unwrap();
+    // `init_logger`/`get_or_set_runtime` run once at module import, before any `DatabasePython`
+    // exists to carry per-instance settings, so the only place left to configure them is the
+    // environment. A real `log_level`/`worker_threads` constructor param on `DatabasePython`
+    // would still need to forward into these same globals on first use -- that forwarding lives
+    // in `database.rs`, which is not part of this tree, so it isn't done here.
+    let log_level = std::env::var("PGML_LOG_LEVEL").unwrap_or_else(|_| "error".to_string());
+    let log_json = std::env::var("PGML_LOG_JSON").is_ok();
+    init_logger(&log_level, log_json);
+
+    let worker_threads = std::env::var("PGML_WORKER_THREADS")
+        .ok()
+        .and_then(|v| v.parse().ok());
+    get_or_set_runtime(worker_threads);
+
     m.add_class::<DatabasePython>()?;
     Ok(())
 }
@@ -88,9 +133,16 @@ mod tests {
         Database::new(CONNECTION_STRING).await.unwrap();
     }
 
+    #[tokio::test]
+    async fn shared_pool_is_reused_across_calls() {
+        let pool = get_or_set_pool(CONNECTION_STRING, Some(5)).await;
+        let pool_again = get_or_set_pool(CONNECTION_STRING, Some(20)).await;
+        assert!(std::ptr::eq(pool, pool_again));
+    }
+
     #[tokio::test]
     async fn can_create_collection_and_vector_search() {
-        init_logger(LevelFilter::Warn).unwrap();
+        init_logger("warn", false);
         let collection_name = "test11";
 
         let db = Database::new(CONNECTION_STRING).await.unwrap();